@@ -0,0 +1,158 @@
+use async_session::chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// SameSite controls the `SameSite` attribute on the emitted cookie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSiteCookieOption {
+    Strict,
+    Lax,
+    None,
+}
+
+impl std::fmt::Display for SameSiteCookieOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SameSiteCookieOption::Strict => write!(f, "Strict"),
+            SameSiteCookieOption::Lax => write!(f, "Lax"),
+            SameSiteCookieOption::None => write!(f, "None"),
+        }
+    }
+}
+
+/// CookieOptions holds everything needed to render the Set-Cookie header
+/// that carries a session's ID to the browser.
+#[derive(Debug, Clone)]
+pub struct CookieOptions {
+    pub cookie_name: String,
+    pub cookie_value: Option<String>,
+    pub max_age: Option<i64>,
+    /// Mirrors `max_age` as an absolute timestamp. Set automatically from
+    /// the session's own expiry by `WithSession::new`; browsers that
+    /// ignore `Max-Age` still fall back to this.
+    pub expires: Option<DateTime<Utc>>,
+    /// When true, a response that didn't change the session's data still
+    /// re-emits its cookie, so an active user's Max-Age keeps resetting
+    /// instead of counting down to expiry while idle users time out as
+    /// usual.
+    pub rolling: bool,
+    /// The full session lifetime `rolling` resets to on every request,
+    /// e.g. `Duration::from_secs(30 * 60)` for a 30-minute idle timeout.
+    /// Without this, `rolling` re-emits the cookie but the session's
+    /// own expiry keeps counting down to its original deadline, so an
+    /// active user would still get logged out on schedule. With this
+    /// set, the refreshed cookie is only emitted when the store's
+    /// `store_session` returns a cookie value; when it returns `None`,
+    /// only the server-side expiry is extended and no cookie is sent.
+    pub rolling_ttl: Option<std::time::Duration>,
+    pub path: Option<String>,
+    pub domain: Option<String>,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<SameSiteCookieOption>,
+    /// When set, the cookie value is HMAC-SHA256 signed using this key
+    /// rather than written out verbatim, so a tampered or guessed ID is
+    /// rejected instead of loaded. Opt-in to keep existing unsigned
+    /// deployments working unchanged.
+    pub key: Option<Vec<u8>>,
+    /// Set this when `session_store` is a [`crate::CookieStore`]: the
+    /// store already returns a fully serialized and signed cookie value,
+    /// so the usual ID signing step is skipped on both the store and
+    /// load paths.
+    pub stateless: bool,
+}
+
+impl Default for CookieOptions {
+    fn default() -> Self {
+        Self {
+            cookie_name: "sid".to_string(),
+            cookie_value: None,
+            max_age: None,
+            expires: None,
+            rolling: false,
+            rolling_ttl: None,
+            path: Some("/".to_string()),
+            domain: None,
+            secure: true,
+            http_only: true,
+            same_site: Some(SameSiteCookieOption::Strict),
+            key: None,
+            stateless: false,
+        }
+    }
+}
+
+impl std::fmt::Display for CookieOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}={}",
+            self.cookie_name,
+            self.cookie_value.as_deref().unwrap_or("")
+        )?;
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age)?;
+        }
+        if let Some(expires) = self.expires {
+            write!(f, "; Expires={}", expires.format("%a, %d %b %Y %H:%M:%S GMT"))?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, "; Path={}", path)?;
+        }
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain={}", domain)?;
+        }
+        if self.secure {
+            write!(f, "; Secure")?;
+        }
+        if self.http_only {
+            write!(f, "; HttpOnly")?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(f, "; SameSite={}", same_site)?;
+        }
+        Ok(())
+    }
+}
+
+impl CookieOptions {
+    /// Signs `session_id` with `key` (if set) into the value that should
+    /// actually be written to the cookie: `base64(HMAC-SHA256(key, id)).id`.
+    /// Returns `session_id` unchanged when no key is configured.
+    pub(crate) fn sign(&self, session_id: &str) -> String {
+        match &self.key {
+            Some(key) => {
+                let tag = compute_tag(key, session_id);
+                format!("{}.{}", base64::encode(tag), session_id)
+            }
+            None => session_id.to_string(),
+        }
+    }
+
+    /// Verifies a cookie value produced by `sign` and returns the bare
+    /// session ID on success. Returns `cookie_value` unchanged when no
+    /// key is configured, so unsigned deployments keep working.
+    pub(crate) fn verify(&self, cookie_value: &str) -> Option<String> {
+        let key = match &self.key {
+            Some(key) => key,
+            None => return Some(cookie_value.to_string()),
+        };
+
+        let (encoded_tag, session_id) = cookie_value.rsplit_once('.')?;
+        let tag = base64::decode(encoded_tag).ok()?;
+
+        let mut mac = HmacSha256::new_from_slice(key).ok()?;
+        mac.update(session_id.as_bytes());
+        mac.verify_slice(&tag).ok()?;
+
+        Some(session_id.to_string())
+    }
+}
+
+fn compute_tag(key: &[u8], session_id: &str) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(session_id.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
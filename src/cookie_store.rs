@@ -0,0 +1,155 @@
+use crate::error::SessionError;
+use async_session::{async_trait, Result as SessionResult, Session, SessionStore};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Per-domain cookie size most browsers enforce (RFC 6265 suggests 4096
+/// bytes). Only the cookie value is measured against it; the `name=`
+/// prefix and attributes that `CookieOptions` adds are not counted.
+const MAX_COOKIE_BYTES: usize = 4093;
+
+/// CookieStore is a [`SessionStore`] that keeps no server-side state at
+/// all: the whole session is serialized to JSON, HMAC-signed, and
+/// round-tripped through the cookie itself. Pair it with
+/// `CookieOptions { stateless: true, .. }` so the session layer writes
+/// its output straight to the cookie instead of signing it again.
+#[derive(Debug, Clone)]
+pub struct CookieStore {
+    key: Vec<u8>,
+}
+
+impl CookieStore {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self { key }
+    }
+
+    fn sign(&self, payload: &[u8]) -> String {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        let tag = mac.finalize().into_bytes();
+        format!("{}.{}", base64::encode(tag), base64::encode(payload))
+    }
+
+    fn verify(&self, cookie_value: &str) -> Option<Vec<u8>> {
+        let (encoded_tag, encoded_payload) = cookie_value.split_once('.')?;
+        let tag = base64::decode(encoded_tag).ok()?;
+        let payload = base64::decode(encoded_payload).ok()?;
+
+        let mut mac = HmacSha256::new_from_slice(&self.key).ok()?;
+        mac.update(&payload);
+        mac.verify_slice(&tag).ok()?;
+
+        Some(payload)
+    }
+}
+
+#[async_trait]
+impl SessionStore for CookieStore {
+    async fn load_session(&self, cookie_value: String) -> SessionResult<Option<Session>> {
+        let payload = match self.verify(&cookie_value) {
+            Some(payload) => payload,
+            None => return Ok(None),
+        };
+
+        let session: Session =
+            serde_json::from_slice(&payload).map_err(|source| SessionError::JsonError { source })?;
+
+        // The backend is the cookie itself, so nothing ever prunes an
+        // expired entry the way a real store would; without this an
+        // expired stateless session would still load as valid forever.
+        Ok(session.validate())
+    }
+
+    async fn store_session(&self, session: Session) -> SessionResult<Option<String>> {
+        let payload = serde_json::to_vec(&session)
+            .map_err(|source| SessionError::JsonError { source })?;
+        let cookie_value = self.sign(&payload);
+
+        if cookie_value.len() > MAX_COOKIE_BYTES {
+            return Err(SessionError::CookieTooLarge {
+                size: cookie_value.len(),
+                limit: MAX_COOKIE_BYTES,
+            }
+            .into());
+        }
+
+        Ok(Some(cookie_value))
+    }
+
+    async fn destroy_session(&self, _session: Session) -> SessionResult {
+        // Nothing to clean up server-side; WithSession::new already
+        // overwrites the cookie with an expiring, empty value.
+        Ok(())
+    }
+
+    async fn clear_store(&self) -> SessionResult {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CookieStore;
+    use async_session::{Session, SessionStore};
+
+    #[tokio::test]
+    async fn test_store_then_load_round_trips_session_data() {
+        let store = CookieStore::new(b"a very secret key".to_vec());
+        let mut session = Session::new();
+        session.insert("key", "value").unwrap();
+
+        let cookie_value = store.store_session(session).await.unwrap().unwrap();
+
+        let loaded = store
+            .load_session(cookie_value)
+            .await
+            .unwrap()
+            .expect("signed cookie should load back into a session");
+        assert_eq!(loaded.get::<String>("key"), Some("value".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_load_rejects_expired_session() {
+        let store = CookieStore::new(b"a very secret key".to_vec());
+        let mut session = Session::new();
+        session.expire_in(std::time::Duration::from_millis(1));
+
+        let cookie_value = store.store_session(session).await.unwrap().unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        assert!(store.load_session(cookie_value).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_load_rejects_tampered_cookie() {
+        let store = CookieStore::new(b"a very secret key".to_vec());
+        let mut session = Session::new();
+        session.insert("key", "value").unwrap();
+
+        let mut cookie_value = store.store_session(session).await.unwrap().unwrap();
+        cookie_value.push('x');
+
+        assert!(store.load_session(cookie_value).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_store_rejects_oversized_payload() {
+        let store = CookieStore::new(b"a very secret key".to_vec());
+        let mut session = Session::new();
+        session.insert("key", "x".repeat(8192)).unwrap();
+
+        assert!(store.store_session(session).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_destroy_and_clear_are_no_ops() {
+        let store = CookieStore::new(b"a very secret key".to_vec());
+        let session = Session::new();
+
+        store.destroy_session(session).await.unwrap();
+        store.clear_store().await.unwrap();
+    }
+}
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+/// SessionError covers the things that can go wrong while binding a
+/// session to a reply or reading/writing it, so callers can turn them
+/// into a warp::Rejection with `?`.
+#[derive(Debug, Error)]
+pub enum SessionError {
+    #[error("error destroying session: {source}")]
+    DestroyError { source: async_session::Error },
+
+    #[error("error storing session: {source}")]
+    StoreError { source: async_session::Error },
+
+    #[error("serialized session of {size} bytes exceeds the {limit} byte cookie size limit")]
+    CookieTooLarge { size: usize, limit: usize },
+
+    #[error("session path `{path}` does not resolve to the requested type")]
+    PathTypeMismatch { path: String },
+
+    #[error("error serializing or deserializing session value: {source}")]
+    JsonError {
+        #[from]
+        source: serde_json::Error,
+    },
+}
+
+impl warp::reject::Reject for SessionError {}
@@ -0,0 +1,51 @@
+use crate::cookie::CookieOptions;
+use crate::error::SessionError;
+use crate::session::SessionWithStore;
+use async_session::{Session, SessionStore};
+use warp::{Filter, Rejection};
+
+/// Builds a warp::Filter that loads the session named by `cookie_options`
+/// out of `session_store`, verifying the signature first if `cookie_options`
+/// carries a signing key. A missing or unverifiable cookie yields a fresh,
+/// empty session rather than a rejection, matching how most session crates
+/// treat an absent session.
+pub fn with_session<S: SessionStore>(
+    session_store: S,
+    cookie_options: CookieOptions,
+) -> impl Filter<Extract = (SessionWithStore<S>,), Error = Rejection> + Clone
+where
+    S: Clone + Send + Sync,
+{
+    warp::filters::cookie::optional(cookie_options.cookie_name.clone()).and_then(
+        move |cookie_value: Option<String>| {
+            let session_store = session_store.clone();
+            let cookie_options = cookie_options.clone();
+            async move {
+                let session_id = if cookie_options.stateless {
+                    // A cookie-backed store owns the whole cookie value
+                    // itself (serialized and signed); hand it over as-is
+                    // and let the store verify/deserialize it.
+                    cookie_value
+                } else {
+                    cookie_value.and_then(|raw| cookie_options.verify(&raw))
+                };
+
+                let session = match session_id {
+                    Some(sid) => session_store
+                        .load_session(sid)
+                        .await
+                        .map_err(|source| SessionError::StoreError { source })?
+                        .unwrap_or_else(Session::new),
+                    None => Session::new(),
+                };
+
+                Ok::<_, Rejection>(SessionWithStore {
+                    session,
+                    session_store,
+                    cookie_options,
+                    regenerate: false,
+                })
+            }
+        },
+    )
+}
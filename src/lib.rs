@@ -0,0 +1,11 @@
+mod cookie;
+mod cookie_store;
+mod error;
+mod filters;
+mod session;
+
+pub use cookie::{CookieOptions, SameSiteCookieOption};
+pub use cookie_store::CookieStore;
+pub use error::SessionError;
+pub use filters::with_session;
+pub use session::{SessionWithStore, WithSession};
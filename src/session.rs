@@ -1,6 +1,9 @@
 use crate::cookie::CookieOptions;
 use crate::error::SessionError;
 use async_session::{Session, SessionStore};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
 use warp::{Rejection, Reply};
 
 /// SessionWithStore binds a session object with its backing store and some cookie options.
@@ -9,6 +12,118 @@ pub struct SessionWithStore<S: SessionStore> {
     pub session: Session,
     pub session_store: S,
     pub cookie_options: CookieOptions,
+    /// Set by [`SessionWithStore::regenerate`]. Tells `WithSession::new`
+    /// to persist this session under a freshly generated ID (and drop
+    /// the old record) instead of reusing the current one.
+    pub regenerate: bool,
+}
+
+impl<S: SessionStore> SessionWithStore<S> {
+    /// Requests that this session be rotated onto a new ID the next time
+    /// it's bound to a reply with `WithSession::new`. Call this right
+    /// after authentication or privilege elevation so a pre-login ID an
+    /// attacker planted in the victim's browser can't ride the newly
+    /// authenticated session.
+    pub fn regenerate(&mut self) {
+        self.regenerate = true;
+    }
+
+    /// Reads a value nested inside a top-level session entry by dotted
+    /// path, e.g. `get_path::<String>("cart.items.0.sku")`. Numeric
+    /// segments index into arrays, everything else indexes into
+    /// objects. Returns `Ok(None)` if any segment along the path is
+    /// missing, and a typed error if the value at the path can't be
+    /// deserialized into `T`.
+    pub fn get_path<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>, SessionError> {
+        let mut segments = path.split('.');
+        let root_key = match segments.next() {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        let root = match self.session.get::<Value>(root_key) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let mut current = &root;
+        for segment in segments {
+            current = match index_value(current, segment) {
+                Some(value) => value,
+                None => return Ok(None),
+            };
+        }
+
+        serde_json::from_value(current.clone())
+            .map(Some)
+            .map_err(|_| SessionError::PathTypeMismatch {
+                path: path.to_string(),
+            })
+    }
+
+    /// Writes a value nested inside a top-level session entry by dotted
+    /// path, e.g. `set_path("user.prefs.theme", "dark")`, creating any
+    /// missing intermediate objects/arrays along the way. Marks the
+    /// session dirty via the underlying `Session::insert`, so the usual
+    /// `data_changed()` check in `WithSession::new` still picks it up.
+    pub fn set_path<T: Serialize>(&mut self, path: &str, value: T) -> Result<(), SessionError> {
+        let mut segments = path.split('.');
+        let root_key = segments
+            .next()
+            .ok_or_else(|| SessionError::PathTypeMismatch {
+                path: path.to_string(),
+            })?
+            .to_string();
+        let remaining: Vec<&str> = segments.collect();
+
+        let mut root = self
+            .session
+            .get::<Value>(&root_key)
+            .unwrap_or(Value::Null);
+        let value = serde_json::to_value(value)?;
+        set_value(&mut root, &remaining, value);
+
+        self.session
+            .insert(&root_key, root)
+            .map_err(|source| SessionError::JsonError { source })?;
+
+        Ok(())
+    }
+}
+
+fn index_value<'a>(value: &'a Value, segment: &str) -> Option<&'a Value> {
+    match segment.parse::<usize>() {
+        Ok(index) => value.as_array()?.get(index),
+        Err(_) => value.as_object()?.get(segment),
+    }
+}
+
+fn set_value(current: &mut Value, segments: &[&str], new_value: Value) {
+    let segment = match segments.first() {
+        Some(segment) => segment,
+        None => {
+            *current = new_value;
+            return;
+        }
+    };
+
+    if let Ok(index) = segment.parse::<usize>() {
+        if !current.is_array() {
+            *current = Value::Array(Vec::new());
+        }
+        let array = current.as_array_mut().expect("just made this an array");
+        while array.len() <= index {
+            array.push(Value::Null);
+        }
+        set_value(&mut array[index], &segments[1..], new_value);
+    } else {
+        if !current.is_object() {
+            *current = Value::Object(serde_json::Map::new());
+        }
+        let object = current.as_object_mut().expect("just made this an object");
+        let entry = object.entry(segment.to_string()).or_insert(Value::Null);
+        set_value(entry, &segments[1..], new_value);
+    }
 }
 
 /// WithSession is a warp::Reply that attaches a session ID in the form of
@@ -29,7 +144,7 @@ where
     /// the cookie.
     pub async fn new<S: SessionStore>(
         reply: T,
-        session_with_store: SessionWithStore<S>,
+        mut session_with_store: SessionWithStore<S>,
     ) -> Result<WithSession<T>, Rejection> {
         let mut cookie_options = session_with_store.cookie_options;
 
@@ -43,16 +158,96 @@ where
                 .await
                 .map_err(|source| SessionError::DestroyError { source })?;
         } else {
-            if session_with_store.session.data_changed() {
+            let old_session = if session_with_store.regenerate {
+                let old_session = session_with_store.session.clone();
+                session_with_store.session.regenerate();
+                Some(old_session)
+            } else {
+                None
+            };
+
+            // Reset the server-side window before reading back expiry,
+            // so a rolling refresh actually buys the session a fresh
+            // full lifetime instead of just reissuing a cookie around
+            // an expiry that keeps counting down to its original
+            // deadline.
+            let rolling_refreshed = match (cookie_options.rolling, cookie_options.rolling_ttl) {
+                (true, Some(ttl)) => {
+                    session_with_store.session.expire_in(ttl);
+                    true
+                }
+                _ => false,
+            };
+
+            let expiry = session_with_store.session.expiry().copied();
+            let data_changed = session_with_store.session.data_changed();
+            // Session::regenerate() only changes the ID; it doesn't mark
+            // the data as changed. Without this, a handler that called
+            // `regenerate()` without also mutating data would silently
+            // keep riding the old, still-valid ID.
+            let rotating = old_session.is_some();
+            // A rolling, stateless (CookieStore-backed) session has to
+            // re-run store_session to refresh its serialized blob --
+            // re-signing the bare id, as the non-stateless path below
+            // does, would produce a cookie CookieStore::verify rejects
+            // on the next request. A rolling refresh that bumped the
+            // expiry likewise has to go through store_session so the
+            // backend persists the new deadline, not just the cookie.
+            let rolling_stateless = cookie_options.rolling && cookie_options.stateless;
+
+            if data_changed || rotating || rolling_stateless || rolling_refreshed {
                 match session_with_store
                     .session_store
                     .store_session(session_with_store.session)
                     .await
                     .map_err(|source| SessionError::StoreError { source })?
                 {
-                    Some(sid) => cookie_options.cookie_value = Some(sid),
+                    Some(sid) => {
+                        let value = if cookie_options.stateless {
+                            // A cookie-backed store already returns the
+                            // fully serialized and signed payload; signing
+                            // it again would add a second tag that the
+                            // stateless load path never strips.
+                            sid
+                        } else {
+                            cookie_options.sign(&sid)
+                        };
+                        cookie_options.cookie_value = Some(value);
+                    }
                     None => (),
                 }
+
+                // Only drop the old record once the new one is safely
+                // persisted, so a failed store leaves the user with
+                // their original, still-valid session instead of none.
+                if let Some(old_session) = old_session {
+                    session_with_store
+                        .session_store
+                        .destroy_session(old_session)
+                        .await
+                        .map_err(|source| SessionError::DestroyError { source })?;
+                }
+            } else if cookie_options.rolling {
+                // rolling is set but rolling_ttl isn't, so there's no
+                // server-side expiry to reset -- just re-emit the same
+                // signed id. This keeps a cookie with no Max-Age fresh
+                // in the browser, but doesn't extend the session itself.
+                let sid = session_with_store.session.id().to_string();
+                cookie_options.cookie_value = Some(cookie_options.sign(&sid));
+            }
+
+            // Keep the cookie's own lifetime in lockstep with the
+            // session's, so the browser discards it exactly when the
+            // server-side session lapses rather than relying on a
+            // hard-coded Max-Age.
+            if cookie_options.cookie_value.is_some() {
+                if let Some(expiry) = expiry {
+                    let seconds_remaining = (expiry - async_session::chrono::Utc::now())
+                        .num_seconds()
+                        .max(0);
+                    cookie_options.max_age = Some(seconds_remaining);
+                    cookie_options.expires = Some(expiry);
+                }
             }
         }
 
@@ -122,6 +317,7 @@ pub mod tests {
             session,
             session_store,
             cookie_options,
+            regenerate: false,
         };
 
         assert_eq!(session_with_store.session.data_changed(), false);
@@ -141,6 +337,7 @@ pub mod tests {
             session,
             session_store,
             cookie_options,
+            regenerate: false,
         };
 
         assert_eq!(session_with_store.session.data_changed(), true);
@@ -160,6 +357,7 @@ pub mod tests {
             session,
             session_store,
             cookie_options,
+            regenerate: false,
         };
 
         assert_eq!(session_with_store.session.is_destroyed(), true);
@@ -167,4 +365,90 @@ pub mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_rolling_with_ttl_refreshes_session_expiry() {
+        let html_reply = warp::reply::html("".to_string());
+        let mut session = Session::new();
+        session.expire_in(std::time::Duration::from_millis(1));
+        let session_store = MemoryStore::new();
+        let mut cookie_options = CookieOptions::default();
+        cookie_options.rolling = true;
+        cookie_options.rolling_ttl = Some(std::time::Duration::from_secs(3600));
+
+        assert_eq!(session.data_changed(), false);
+
+        let session_with_store = SessionWithStore {
+            session,
+            session_store: session_store.clone(),
+            cookie_options,
+            regenerate: false,
+        };
+
+        let with_session = WithSession::new(html_reply, session_with_store)
+            .await
+            .unwrap();
+
+        let cookie_value = with_session
+            .cookie_options
+            .cookie_value
+            .clone()
+            .expect("a rolling refresh should still emit a cookie");
+
+        let stored = session_store
+            .load_session(cookie_value)
+            .await
+            .unwrap()
+            .expect("the refreshed session should have been persisted, not just re-signed");
+
+        let remaining = stored
+            .expires_in()
+            .expect("session should still carry an expiry");
+        assert!(remaining > std::time::Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_get_set_path_nested() {
+        let mut session_with_store = SessionWithStore {
+            session: Session::new(),
+            session_store: MemoryStore::new(),
+            cookie_options: CookieOptions::default(),
+            regenerate: false,
+        };
+
+        session_with_store
+            .set_path("cart.items.0.sku", "ABC123")
+            .unwrap();
+
+        let sku: Option<String> = session_with_store.get_path("cart.items.0.sku").unwrap();
+        assert_eq!(sku, Some("ABC123".to_string()));
+        assert_eq!(session_with_store.session.data_changed(), true);
+    }
+
+    #[test]
+    fn test_get_path_missing_returns_none() {
+        let session_with_store = SessionWithStore {
+            session: Session::new(),
+            session_store: MemoryStore::new(),
+            cookie_options: CookieOptions::default(),
+            regenerate: false,
+        };
+
+        let value: Option<String> = session_with_store.get_path("user.prefs.theme").unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_get_path_type_mismatch_errors() {
+        let mut session_with_store = SessionWithStore {
+            session: Session::new(),
+            session_store: MemoryStore::new(),
+            cookie_options: CookieOptions::default(),
+            regenerate: false,
+        };
+
+        session_with_store.set_path("user.age", 42).unwrap();
+        let result = session_with_store.get_path::<String>("user.age");
+        assert!(result.is_err());
+    }
 }